@@ -0,0 +1,18 @@
+//! With the `embedded-prover` feature, embeds a prebuilt `cpu_air_prover` binary into the crate
+//! (see `src/prover/embedded.rs`). The binary itself isn't built by this script; it must already
+//! exist on disk, pointed to by the `CPU_AIR_PROVER_BIN` environment variable.
+
+fn main() {
+    if std::env::var_os("CARGO_FEATURE_EMBEDDED_PROVER").is_none() {
+        return;
+    }
+
+    println!("cargo:rerun-if-env-changed=CPU_AIR_PROVER_BIN");
+
+    let prover_path = std::env::var("CPU_AIR_PROVER_BIN").expect(
+        "the embedded-prover feature requires CPU_AIR_PROVER_BIN to point at a built cpu_air_prover binary",
+    );
+
+    println!("cargo:rerun-if-changed={prover_path}");
+    println!("cargo:rustc-env=CPU_AIR_PROVER_PATH={prover_path}");
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+use std::process::Output;
+
+/// Errors that can occur while invoking the Stone Prover or Stone Verifier.
+#[derive(Debug)]
+pub enum ProverError {
+    /// A value passed to one of the crate's functions was invalid (ex: `max_concurrency == 0`,
+    /// a `SerializationFormat` unsupported by the selected `Backend`, a malformed or incomplete
+    /// `memory` buffer). Kept distinct from `IoError` so that callers can tell "you gave me bad
+    /// input, don't retry" apart from a genuine disk or subprocess failure.
+    InvalidArgument(String),
+    /// An I/O error occurred while preparing input files or spawning the prover/verifier.
+    IoError(std::io::Error),
+    /// The prover subprocess exited with a non-zero status. (A non-zero exit of the verifier is
+    /// reported as `Ok(false)`, not as this error.)
+    CommandError(Output),
+    /// Failed to serialize or deserialize one of the prover's JSON models.
+    SerializationError(serde_json::Error),
+    /// Failed to serialize one of the prover's models to MessagePack.
+    MsgPackEncodeError(rmp_serde::encode::Error),
+    /// Failed to deserialize one of the prover's models from MessagePack.
+    MsgPackDecodeError(rmp_serde::decode::Error),
+    /// A value passed to the FFI backend contained an interior NUL byte.
+    #[cfg(feature = "ffi")]
+    NulError(std::ffi::NulError),
+    /// The FFI backend's `cpu_air_prover_run` call returned a non-zero status, or returned 0
+    /// without producing a proof (in which case the status held here is 0).
+    #[cfg(feature = "ffi")]
+    FfiError(std::os::raw::c_int),
+}
+
+impl fmt::Display for ProverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProverError::InvalidArgument(message) => write!(f, "invalid argument: {message}"),
+            ProverError::IoError(err) => write!(f, "I/O error: {err}"),
+            ProverError::CommandError(output) => {
+                write!(f, "command exited with status {}", output.status)
+            }
+            ProverError::SerializationError(err) => write!(f, "serialization error: {err}"),
+            ProverError::MsgPackEncodeError(err) => write!(f, "MessagePack encode error: {err}"),
+            ProverError::MsgPackDecodeError(err) => write!(f, "MessagePack decode error: {err}"),
+            #[cfg(feature = "ffi")]
+            ProverError::NulError(err) => write!(f, "interior NUL byte in FFI input: {err}"),
+            #[cfg(feature = "ffi")]
+            ProverError::FfiError(status) => {
+                write!(f, "FFI prover call failed with status {status}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProverError {}
+
+impl From<std::io::Error> for ProverError {
+    fn from(err: std::io::Error) -> Self {
+        ProverError::IoError(err)
+    }
+}
+
+impl From<serde_json::Error> for ProverError {
+    fn from(err: serde_json::Error) -> Self {
+        ProverError::SerializationError(err)
+    }
+}
+
+impl From<rmp_serde::encode::Error> for ProverError {
+    fn from(err: rmp_serde::encode::Error) -> Self {
+        ProverError::MsgPackEncodeError(err)
+    }
+}
+
+impl From<rmp_serde::decode::Error> for ProverError {
+    fn from(err: rmp_serde::decode::Error) -> Self {
+        ProverError::MsgPackDecodeError(err)
+    }
+}
+
+#[cfg(feature = "ffi")]
+impl From<std::ffi::NulError> for ProverError {
+    fn from(err: std::ffi::NulError) -> Self {
+        ProverError::NulError(err)
+    }
+}
@@ -0,0 +1,223 @@
+//! Tracked, cancellable proving jobs built on top of [`run_prover_async`].
+//!
+//! `run_prover_async` owns the whole proving lifecycle inside one `await`, so a caller that
+//! wants to fire off a proving request, poll for its status later, and optionally cancel it
+//! has no way to do so today. [`ProverJobManager`] fills that gap.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::task::JoinHandle;
+
+use crate::error::ProverError;
+use crate::models::Proof;
+use crate::prover::{run_prover_async, ProverJob};
+
+/// Identifies a job submitted to a [`ProverJobManager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JobId(u64);
+
+/// The current state of a submitted proving job.
+#[derive(Debug)]
+pub enum JobStatus {
+    /// The job has been submitted but has not started running yet.
+    Registered,
+    /// The prover is currently running.
+    WorkInProgress,
+    /// The prover completed successfully.
+    Success(Proof),
+    /// The prover failed.
+    Failed(ProverError),
+    /// The job was cancelled before it completed.
+    Cancelled,
+}
+
+struct JobRecord {
+    status: Arc<Mutex<Arc<JobStatus>>>,
+    handle: JoinHandle<()>,
+    finished_at: Arc<Mutex<Option<Instant>>>,
+}
+
+/// Tracks proving jobs submitted via [`ProverJobManager::submit`], letting callers poll their
+/// status, cancel them, and prune completed entries.
+#[derive(Default)]
+pub struct ProverJobManager {
+    jobs: Mutex<HashMap<JobId, JobRecord>>,
+    next_id: AtomicU64,
+}
+
+impl ProverJobManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawns `job` on the `tokio` runtime and returns a [`JobId`] that can be used to poll its
+    /// status, cancel it, or prune it once it has completed.
+    pub fn submit(&self, job: ProverJob) -> JobId {
+        let id = JobId(self.next_id.fetch_add(1, Ordering::Relaxed));
+
+        let status = Arc::new(Mutex::new(Arc::new(JobStatus::Registered)));
+        let finished_at = Arc::new(Mutex::new(None));
+
+        let task_status = status.clone();
+        let task_finished_at = finished_at.clone();
+        let handle = tokio::spawn(async move {
+            {
+                // `cancel()` may have already run (and recorded `Cancelled`, aborted this very
+                // task) before this task got a chance to run; if so, don't resurrect it as
+                // `WorkInProgress` in the window before the abort takes effect.
+                let mut status = task_status.lock().unwrap();
+                if matches!(**status, JobStatus::Cancelled) {
+                    return;
+                }
+                *status = Arc::new(JobStatus::WorkInProgress);
+            }
+
+            let result = run_prover_async(
+                &job.public_input,
+                &job.memory,
+                &job.trace,
+                &job.prover_config,
+                &job.parameters,
+                &job.ecdsa_signatures,
+            )
+            .await;
+
+            // `cancel()` may have run (and recorded `Cancelled`) in the window between
+            // `run_prover_async` resolving and this task re-acquiring `status`; if so, leave its
+            // result alone instead of clobbering `Cancelled` back to `Success`/`Failed`.
+            let mut status = task_status.lock().unwrap();
+            if !matches!(**status, JobStatus::Cancelled) {
+                *status = Arc::new(match result {
+                    Ok(proof) => JobStatus::Success(proof),
+                    Err(err) => JobStatus::Failed(err),
+                });
+                drop(status);
+                *task_finished_at.lock().unwrap() = Some(Instant::now());
+            }
+        });
+
+        self.jobs.lock().unwrap().insert(
+            id,
+            JobRecord {
+                status,
+                handle,
+                finished_at,
+            },
+        );
+
+        id
+    }
+
+    /// Returns the current status of `job_id`, or `None` if it is unknown (never submitted, or
+    /// already pruned).
+    pub fn status(&self, job_id: JobId) -> Option<Arc<JobStatus>> {
+        let jobs = self.jobs.lock().unwrap();
+        let record = jobs.get(&job_id)?;
+        Some(record.status.lock().unwrap().clone())
+    }
+
+    /// Cancels `job_id`, aborting the underlying `tokio` task (which, together with
+    /// `kill_on_drop` on the prover's `Command`, also kills the child `cpu_air_prover` process
+    /// if it was already running). Does nothing if the job is unknown or already finished.
+    pub fn cancel(&self, job_id: JobId) {
+        let jobs = self.jobs.lock().unwrap();
+        let Some(record) = jobs.get(&job_id) else {
+            return;
+        };
+
+        let mut status = record.status.lock().unwrap();
+        if matches!(**status, JobStatus::Registered | JobStatus::WorkInProgress) {
+            record.handle.abort();
+            *status = Arc::new(JobStatus::Cancelled);
+            *record.finished_at.lock().unwrap() = Some(Instant::now());
+        }
+    }
+
+    /// Drops completed, failed or cancelled jobs that finished at least `older_than` ago, to
+    /// bound the manager's memory usage.
+    pub fn prune(&self, older_than: Duration) {
+        let now = Instant::now();
+        self.jobs
+            .lock()
+            .unwrap()
+            .retain(|_, record| match *record.finished_at.lock().unwrap() {
+                Some(finished_at) => now.duration_since(finished_at) < older_than,
+                None => true,
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::prover::test_support::fibonacci_job;
+
+    use super::*;
+
+    /// Polls `job_id`'s status until it leaves `Registered`/`WorkInProgress`, or panics after a
+    /// generous timeout.
+    async fn wait_for_completion(manager: &ProverJobManager, job_id: JobId) -> Arc<JobStatus> {
+        for _ in 0..200 {
+            let status = manager.status(job_id).expect("job should still be tracked");
+            if !matches!(*status, JobStatus::Registered | JobStatus::WorkInProgress) {
+                return status;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("job did not complete in time");
+    }
+
+    #[tokio::test]
+    async fn test_submit_and_status_happy_path() {
+        // Add build dir to path for the duration of the test
+        let path = std::env::var("PATH").unwrap_or_default();
+        let build_dir = env!("OUT_DIR");
+        std::env::set_var("PATH", format!("{build_dir}:{path}"));
+
+        let manager = ProverJobManager::new();
+        let job_id = manager.submit(fibonacci_job());
+
+        let status = wait_for_completion(&manager, job_id).await;
+
+        assert!(matches!(*status, JobStatus::Success(_)));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_transitions_registered_job_to_cancelled() {
+        let manager = ProverJobManager::new();
+        let job_id = manager.submit(fibonacci_job());
+
+        // No `.await` has run yet, so the spawned task hasn't had a chance to start: this always
+        // cancels a `Registered` job.
+        manager.cancel(job_id);
+
+        let status = manager.status(job_id).unwrap();
+        assert!(matches!(*status, JobStatus::Cancelled));
+    }
+
+    #[tokio::test]
+    async fn test_prune_respects_older_than_and_never_reaps_unfinished() {
+        let manager = ProverJobManager::new();
+
+        // Never polled, so it's still `Registered` and `finished_at` is `None`.
+        let unfinished = manager.submit(fibonacci_job());
+
+        // Cancelling finishes a job immediately.
+        let cancelled = manager.submit(fibonacci_job());
+        manager.cancel(cancelled);
+
+        // `older_than` bigger than the elapsed time: nothing should be pruned yet.
+        manager.prune(Duration::from_secs(3600));
+        assert!(manager.status(unfinished).is_some());
+        assert!(manager.status(cancelled).is_some());
+
+        // A zero `older_than` prunes anything finished, but must never touch `unfinished`.
+        manager.prune(Duration::from_secs(0));
+        assert!(manager.status(unfinished).is_some());
+        assert!(manager.status(cancelled).is_none());
+    }
+}
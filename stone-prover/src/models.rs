@@ -42,10 +42,46 @@ pub struct ProverParameters {
 pub struct PrivateInput {
     pub memory_path: PathBuf,
     pub trace_path: PathBuf,
-    // TODO: the types for the 3 fields below are not clear, ask for a spec.
-    pub pedersen: Vec<u32>,
-    pub range_check: Vec<u32>,
-    pub ecdsa: Vec<u32>,
+    pub pedersen: Vec<PedersenInput>,
+    pub range_check: Vec<RangeCheckInput>,
+    pub ecdsa: Vec<EcdsaInput>,
+}
+
+/// One Pedersen hash builtin instance: the two inputs occupying its memory cells.
+///
+/// The third (output) cell is computed by the prover and isn't part of the private input.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PedersenInput {
+    pub index: u32,
+    pub x: String,
+    pub y: String,
+}
+
+/// One range-check builtin instance: the value occupying its memory cell.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RangeCheckInput {
+    pub index: u32,
+    pub value: String,
+}
+
+/// The (r, w) signature backing one ECDSA builtin instance.
+///
+/// This isn't part of the public memory layout, so it can't be derived from `PublicInput` and
+/// must be supplied by the caller alongside it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EcdsaSignatureInput {
+    pub r: String,
+    pub w: String,
+}
+
+/// One ECDSA builtin instance: the public key and message occupying its memory cells, plus the
+/// signature backing them.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EcdsaInput {
+    pub index: u32,
+    pub pubkey: String,
+    pub message: String,
+    pub signature_input: EcdsaSignatureInput,
 }
 
 #[derive(Serialize, Deserialize, Eq, PartialEq, Debug)]
@@ -102,7 +138,7 @@ pub struct PublicInput {
     pub dynamic_params: Option<HashMap<String, u32>>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Proof {
     // Note: we only map output fields for now
     pub proof_hex: String,
@@ -130,9 +166,9 @@ mod tests {
             private_input.trace_path,
             Path::new("/home/root/fibonacci_trace.json")
         );
-        assert_eq!(private_input.pedersen, Vec::<u32>::new());
-        assert_eq!(private_input.range_check, Vec::<u32>::new());
-        assert_eq!(private_input.ecdsa, Vec::<u32>::new());
+        assert!(private_input.pedersen.is_empty());
+        assert!(private_input.range_check.is_empty());
+        assert!(private_input.ecdsa.is_empty());
     }
 
     /// Sanity check: verify that we can deserialize a public input JSON file.
@@ -1,15 +1,46 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use futures::stream::{self, StreamExt};
 use tempfile::tempdir;
 
 use crate::error::ProverError;
-use crate::models::{PrivateInput, Proof, ProverConfig, ProverParameters, PublicInput};
-use crate::toolkit::{read_json_from_file, write_json_to_file};
+use crate::models::{
+    EcdsaInput, EcdsaSignatureInput, MemorySegment, PedersenInput, PrivateInput, Proof,
+    ProverConfig, ProverParameters, PublicInput, RangeCheckInput,
+};
+use crate::toolkit::{read_json_from_file, write_to_file, SerializationFormat};
+
+#[cfg(feature = "ffi")]
+mod ffi;
+
+#[cfg(feature = "embedded-prover")]
+mod embedded;
+
+/// Selects how the Stone Prover is invoked.
+///
+/// * `Subprocess`: shell out to the `cpu_air_prover` binary, round-tripping inputs and the
+///                 proof through temporary files. This is the default and requires no special
+///                 build configuration.
+/// * `Ffi`: call into the Stone Prover shared library directly, passing the public input,
+///          memory and trace in memory. Avoids the per-call subprocess and temp-file overhead,
+///          at the cost of linking the native library. Only available with the `ffi` feature.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum Backend {
+    #[default]
+    Subprocess,
+    #[cfg(feature = "ffi")]
+    Ffi,
+}
 
 /// Call the Stone Prover from the command line.
 ///
 /// Input files must be prepared by the caller.
 ///
+/// * `prover_binary`: Path to the `cpu_air_prover` executable. With the `embedded-prover`
+///                    feature this is an absolute path to the binary extracted by
+///                    `prepare_prover_files`; otherwise it is just `"cpu_air_prover"`, resolved
+///                    against `PATH`.
 /// * `public_input_file`: Path to the public input file.
 /// * `private_input_file`: Path to the private input file. The private input file points to
 ///                         the memory and trace files.
@@ -20,13 +51,14 @@ use crate::toolkit::{read_json_from_file, write_json_to_file};
 /// * `output_file`: Path to the proof file. This function will write the generated proof
 ///                  as JSON to this file.
 pub fn run_prover_from_command_line(
+    prover_binary: &Path,
     public_input_file: &Path,
     private_input_file: &Path,
     prover_config_file: &Path,
     prover_parameter_file: &Path,
     output_file: &Path,
 ) -> Result<(), ProverError> {
-    let output = std::process::Command::new("cpu_air_prover")
+    let output = std::process::Command::new(prover_binary)
         .arg("--out-file")
         .arg(output_file)
         .arg("--public-input-file")
@@ -50,6 +82,8 @@ pub fn run_prover_from_command_line(
 ///
 /// Input files must be prepared by the caller.
 ///
+/// * `prover_binary`: Path to the `cpu_air_prover` executable. See
+///                    [`run_prover_from_command_line`] for details.
 /// * `public_input_file`: Path to the public input file.
 /// * `private_input_file`: Path to the private input file. The private input file points to
 ///                         the memory and trace files.
@@ -60,13 +94,17 @@ pub fn run_prover_from_command_line(
 /// * `output_file`: Path to the proof file. This function will write the generated proof
 ///                  as JSON to this file.
 pub async fn run_prover_from_command_line_async(
+    prover_binary: &Path,
     public_input_file: &Path,
     private_input_file: &Path,
     prover_config_file: &Path,
     parameter_file: &Path,
     output_file: &Path,
 ) -> Result<(), ProverError> {
-    let output = tokio::process::Command::new("cpu_air_prover")
+    // `kill_on_drop` ensures that aborting the enclosing task (ex: via `ProverJobManager::cancel`)
+    // also terminates the child process instead of leaving it running in the background.
+    let output = tokio::process::Command::new(prover_binary)
+        .kill_on_drop(true)
         .arg("--out-file")
         .arg(output_file)
         .arg("--public-input-file")
@@ -87,8 +125,121 @@ pub async fn run_prover_from_command_line_async(
     Ok(())
 }
 
+/// Call the Stone Verifier from the command line.
+///
+/// Input files must be prepared by the caller.
+///
+/// * `proof_file`: Path to the proof file to verify, as generated by `run_prover`.
+/// * `annotation_file`: Path to the annotation file produced alongside the proof.
+/// * `extra_output_file`: Path to the extra output file produced alongside the proof.
+///
+/// Returns `true` if the proof is valid, `false` otherwise.
+pub fn run_verifier_from_command_line(
+    proof_file: &Path,
+    annotation_file: &Path,
+    extra_output_file: &Path,
+) -> Result<bool, ProverError> {
+    let output = std::process::Command::new("cpu_air_verifier")
+        .arg("--in-file")
+        .arg(proof_file)
+        .arg("--annotation-file")
+        .arg(annotation_file)
+        .arg("--extra-output-file")
+        .arg(extra_output_file)
+        .output()?;
+
+    Ok(output.status.success())
+}
+
+/// Call the Stone Verifier from the command line, asynchronously.
+///
+/// Input files must be prepared by the caller.
+///
+/// * `proof_file`: Path to the proof file to verify, as generated by `run_prover`.
+/// * `annotation_file`: Path to the annotation file produced alongside the proof.
+/// * `extra_output_file`: Path to the extra output file produced alongside the proof.
+///
+/// Returns `true` if the proof is valid, `false` otherwise.
+pub async fn run_verifier_from_command_line_async(
+    proof_file: &Path,
+    annotation_file: &Path,
+    extra_output_file: &Path,
+) -> Result<bool, ProverError> {
+    let output = tokio::process::Command::new("cpu_air_verifier")
+        .arg("--in-file")
+        .arg(proof_file)
+        .arg("--annotation-file")
+        .arg(annotation_file)
+        .arg("--extra-output-file")
+        .arg(extra_output_file)
+        .output()
+        .await?;
+
+    Ok(output.status.success())
+}
+
+/// Bundles the inputs of a single `run_prover` call so that many proving tasks can be queued
+/// up and run together with [`run_prover_batch`].
+pub struct ProverJob {
+    pub public_input: PublicInput,
+    pub memory: Vec<u8>,
+    pub trace: Vec<u8>,
+    pub prover_config: ProverConfig,
+    pub parameters: ProverParameters,
+    /// Signatures for the program's ECDSA builtin instances, one per instance, in the same
+    /// order they appear in the ECDSA memory segment. Leave empty for programs that don't use
+    /// the ECDSA builtin.
+    pub ecdsa_signatures: Vec<EcdsaSignatureInput>,
+}
+
+/// Builds the error returned when `run_prover_batch` is asked to run with `max_concurrency ==
+/// 0`. `buffer_unordered(0)` never polls the underlying stream, so proceeding would hang
+/// forever instead of making progress; reject it up front instead.
+fn invalid_concurrency_error() -> ProverError {
+    ProverError::InvalidArgument(
+        "run_prover_batch: max_concurrency must be greater than zero".to_string(),
+    )
+}
+
+/// Run many proving jobs concurrently, capping the number of `cpu_air_prover` subprocesses
+/// running at any given time to `max_concurrency`.
+///
+/// Results are returned in the same order as `jobs`, regardless of completion order.
+///
+/// Returns an `InvalidArgument` error if `max_concurrency` is 0.
+pub async fn run_prover_batch(
+    jobs: Vec<ProverJob>,
+    max_concurrency: usize,
+) -> Result<Vec<Result<Proof, ProverError>>, ProverError> {
+    if max_concurrency == 0 {
+        return Err(invalid_concurrency_error());
+    }
+
+    let mut results: Vec<(usize, Result<Proof, ProverError>)> =
+        stream::iter(jobs.into_iter().enumerate())
+            .map(|(index, job)| async move {
+                let result = run_prover_async(
+                    &job.public_input,
+                    &job.memory,
+                    &job.trace,
+                    &job.prover_config,
+                    &job.parameters,
+                    &job.ecdsa_signatures,
+                )
+                .await;
+                (index, result)
+            })
+            .buffer_unordered(max_concurrency)
+            .collect()
+            .await;
+
+    results.sort_by_key(|(index, _)| *index);
+    Ok(results.into_iter().map(|(_, result)| result).collect())
+}
+
 struct ProverWorkingDirectory {
     _dir: tempfile::TempDir,
+    prover_binary: PathBuf,
     public_input_file: PathBuf,
     private_input_file: PathBuf,
     _memory_file: PathBuf,
@@ -98,47 +249,273 @@ struct ProverWorkingDirectory {
     proof_file: PathBuf,
 }
 
+struct VerifierWorkingDirectory {
+    _dir: tempfile::TempDir,
+    proof_file: PathBuf,
+    annotation_file: PathBuf,
+    extra_output_file: PathBuf,
+}
+
+/// Builds the error returned when `Backend::Subprocess` is asked to use a format other than
+/// JSON. `cpu_air_prover` has no flag to select its input codec, so it can only ever be handed
+/// JSON files.
+fn unsupported_subprocess_format_error() -> ProverError {
+    ProverError::InvalidArgument(
+        "Backend::Subprocess only supports SerializationFormat::Json: cpu_air_prover has no \
+         flag to select a different input codec"
+            .to_string(),
+    )
+}
+
+/// Builds the error returned when `Backend::Ffi` is asked to use a format other than JSON.
+/// `ffi::run_prover_ffi` always serializes its inputs with `serde_json` before calling into the
+/// native library, regardless of the caller's requested `format`, so a caller-requested
+/// `MsgPack` must be rejected up front rather than silently proved as JSON anyway.
+#[cfg(feature = "ffi")]
+fn unsupported_ffi_format_error() -> ProverError {
+    ProverError::InvalidArgument(
+        "Backend::Ffi only supports SerializationFormat::Json: it always serializes its inputs \
+         to JSON before calling into the native library"
+            .to_string(),
+    )
+}
+
+/// Builds the error returned when `Backend::Ffi` is asked to prove a program that uses the
+/// ECDSA builtin. `cpu_air_prover_run` has no parameter to pass `ecdsa_signatures` through, and
+/// the (r, w) signature isn't part of the public memory layout, so the FFI backend has no way
+/// to derive it on its own; it must be rejected rather than silently proved with an empty one.
+#[cfg(feature = "ffi")]
+fn unsupported_ffi_ecdsa_error() -> ProverError {
+    ProverError::InvalidArgument(
+        "Backend::Ffi cannot prove programs using the ECDSA builtin: cpu_air_prover_run has no \
+         parameter to pass ecdsa_signatures through"
+            .to_string(),
+    )
+}
+
+/// Returns the file extension conventionally used for files encoded with `format`.
+fn format_extension(format: SerializationFormat) -> &'static str {
+    match format {
+        SerializationFormat::Json => "json",
+        SerializationFormat::MsgPack => "msgpack",
+    }
+}
+
+const CAIRO_MEMORY_CELL_SIZE: usize = 40;
+const CAIRO_FELT_SIZE: usize = 32;
+
+/// Parses a `memory.bin` file into a map from memory address to the felt value stored there.
+///
+/// Each cell is a little-endian `u64` address followed by a little-endian felt value.
+///
+/// Returns an error if `memory` isn't an exact multiple of [`CAIRO_MEMORY_CELL_SIZE`], rather
+/// than silently dropping the trailing partial cell.
+fn parse_memory_cells(memory: &[u8]) -> Result<HashMap<u64, [u8; CAIRO_FELT_SIZE]>, ProverError> {
+    if memory.len() % CAIRO_MEMORY_CELL_SIZE != 0 {
+        return Err(ProverError::InvalidArgument(format!(
+            "memory is {} bytes, not a multiple of the {CAIRO_MEMORY_CELL_SIZE}-byte Cairo \
+             memory cell size: malformed memory.bin",
+            memory.len()
+        )));
+    }
+
+    Ok(memory
+        .chunks_exact(CAIRO_MEMORY_CELL_SIZE)
+        .map(|cell| {
+            let address = u64::from_le_bytes(cell[0..8].try_into().unwrap());
+            let mut value = [0u8; CAIRO_FELT_SIZE];
+            value.copy_from_slice(&cell[8..CAIRO_MEMORY_CELL_SIZE]);
+            (address, value)
+        })
+        .collect())
+}
+
+/// Builds the error returned when a builtin's memory segment references an address with no
+/// corresponding cell in `memory`. A gap inside `[begin_addr, stop_ptr)` means the public memory
+/// is malformed, so this must be a hard error rather than a quietly-shorter private input list.
+fn missing_memory_cell_error(builtin: &str, address: u64) -> ProverError {
+    ProverError::InvalidArgument(format!(
+        "{builtin} builtin instance at memory address {address} has no corresponding cell \
+         in `memory`: the public memory is missing an entry inside the builtin's segment"
+    ))
+}
+
+/// Formats a little-endian felt value as a `0x`-prefixed big-endian hex string, as expected by
+/// the Stone Prover's private input JSON.
+fn felt_to_hex(value: &[u8; CAIRO_FELT_SIZE]) -> String {
+    let mut hex = String::with_capacity(2 + CAIRO_FELT_SIZE * 2);
+    hex.push_str("0x");
+    for byte in value.iter().rev() {
+        hex.push_str(&format!("{byte:02x}"));
+    }
+    hex
+}
+
+/// Derives the Pedersen builtin's private input (the two input cells of each instance; the
+/// output cell is computed by the prover) from its memory segment.
+///
+/// Returns an error if an instance's cells are missing from `memory`, rather than silently
+/// dropping that instance from the private input.
+fn derive_pedersen_input(
+    segment: &MemorySegment,
+    memory: &HashMap<u64, [u8; CAIRO_FELT_SIZE]>,
+) -> Result<Vec<PedersenInput>, ProverError> {
+    const CELLS_PER_INSTANCE: u64 = 3;
+    (segment.begin_addr as u64..segment.stop_ptr as u64)
+        .step_by(CELLS_PER_INSTANCE as usize)
+        .enumerate()
+        .map(|(index, address)| {
+            let x = memory
+                .get(&address)
+                .ok_or_else(|| missing_memory_cell_error("pedersen", address))?;
+            let y = memory
+                .get(&(address + 1))
+                .ok_or_else(|| missing_memory_cell_error("pedersen", address + 1))?;
+            Ok(PedersenInput {
+                index: index as u32,
+                x: felt_to_hex(x),
+                y: felt_to_hex(y),
+            })
+        })
+        .collect()
+}
+
+/// Derives the range-check builtin's private input (the value held in each instance's single
+/// cell) from its memory segment.
+///
+/// Returns an error if an instance's cell is missing from `memory`, rather than silently
+/// dropping that instance from the private input.
+fn derive_range_check_input(
+    segment: &MemorySegment,
+    memory: &HashMap<u64, [u8; CAIRO_FELT_SIZE]>,
+) -> Result<Vec<RangeCheckInput>, ProverError> {
+    (segment.begin_addr as u64..segment.stop_ptr as u64)
+        .enumerate()
+        .map(|(index, address)| {
+            let value = memory
+                .get(&address)
+                .ok_or_else(|| missing_memory_cell_error("range-check", address))?;
+            Ok(RangeCheckInput {
+                index: index as u32,
+                value: felt_to_hex(value),
+            })
+        })
+        .collect()
+}
+
+/// Derives the ECDSA builtin's private input (the public key and message held in each
+/// instance's two cells) from its memory segment, pairing each instance with the
+/// caller-supplied signature at the same index in `signatures`.
+///
+/// The (r, w) signature backing each instance isn't part of the public memory layout and can't
+/// be recovered from it, so it must be supplied by the caller alongside the rest of the prover
+/// input. Returns an error if the segment holds an instance for which `signatures` has no
+/// matching entry, rather than silently baking in an empty signature.
+fn derive_ecdsa_input(
+    segment: &MemorySegment,
+    memory: &HashMap<u64, [u8; CAIRO_FELT_SIZE]>,
+    signatures: &[EcdsaSignatureInput],
+) -> Result<Vec<EcdsaInput>, ProverError> {
+    const CELLS_PER_INSTANCE: u64 = 2;
+    (segment.begin_addr as u64..segment.stop_ptr as u64)
+        .step_by(CELLS_PER_INSTANCE as usize)
+        .enumerate()
+        .map(|(index, address)| {
+            let pubkey = memory
+                .get(&address)
+                .ok_or_else(|| missing_memory_cell_error("ecdsa", address))?;
+            let message = memory
+                .get(&(address + 1))
+                .ok_or_else(|| missing_memory_cell_error("ecdsa", address + 1))?;
+            Ok((index, pubkey, message))
+        })
+        .collect::<Result<Vec<_>, ProverError>>()?
+        .into_iter()
+        .map(|(index, pubkey, message)| {
+            let signature_input = signatures.get(index).cloned().ok_or_else(|| {
+                ProverError::InvalidArgument(format!(
+                    "ECDSA builtin instance {index} has no matching entry in \
+                     `ecdsa_signatures`: programs using the ECDSA builtin must supply one \
+                     signature per instance"
+                ))
+            })?;
+            Ok(EcdsaInput {
+                index: index as u32,
+                pubkey: felt_to_hex(pubkey),
+                message: felt_to_hex(message),
+                signature_input,
+            })
+        })
+        .collect()
+}
+
 fn prepare_prover_files(
     public_input: &PublicInput,
     memory: &Vec<u8>,
     trace: &Vec<u8>,
     prover_config: &ProverConfig,
     parameters: &ProverParameters,
-) -> Result<ProverWorkingDirectory, std::io::Error> {
+    ecdsa_signatures: &[EcdsaSignatureInput],
+    format: SerializationFormat,
+) -> Result<ProverWorkingDirectory, ProverError> {
     let tmp_dir = tempdir()?;
 
     let tmp_dir_path = tmp_dir.path();
+    let ext = format_extension(format);
 
-    let public_input_file = tmp_dir_path.join("public_input.json");
-    let private_input_file = tmp_dir_path.join("private_input.json");
+    let public_input_file = tmp_dir_path.join(format!("public_input.{ext}"));
+    let private_input_file = tmp_dir_path.join(format!("private_input.{ext}"));
     let memory_file = tmp_dir_path.join("memory.bin");
-    let prover_config_file = tmp_dir_path.join("prover_config_file.json");
-    let prover_parameter_file = tmp_dir_path.join("parameters.json");
+    let prover_config_file = tmp_dir_path.join(format!("prover_config_file.{ext}"));
+    let prover_parameter_file = tmp_dir_path.join(format!("parameters.{ext}"));
     let trace_file = tmp_dir_path.join("trace.bin");
     let proof_file = tmp_dir_path.join("proof.json");
 
     // Write public input and config/parameters files
-    write_json_to_file(public_input, &public_input_file)?;
-    write_json_to_file(prover_config, &prover_config_file)?;
-    write_json_to_file(parameters, &prover_parameter_file)?;
+    write_to_file(public_input, &public_input_file, format)?;
+    write_to_file(prover_config, &prover_config_file, format)?;
+    write_to_file(parameters, &prover_parameter_file, format)?;
 
     // Write memory and trace files
     std::fs::write(&memory_file, memory)?;
     std::fs::write(&trace_file, trace)?;
 
+    // Derive the builtin segments' private input from the public memory layout, so that
+    // programs using the Pedersen, range-check and ECDSA builtins prove correctly instead of
+    // silently getting an empty private input.
+    let memory_cells = parse_memory_cells(memory)?;
+    let pedersen = derive_pedersen_input(&public_input.memory_segments.pedersen, &memory_cells)?;
+    let range_check =
+        derive_range_check_input(&public_input.memory_segments.range_check, &memory_cells)?;
+    let ecdsa = derive_ecdsa_input(
+        &public_input.memory_segments.ecdsa,
+        &memory_cells,
+        ecdsa_signatures,
+    )?;
+
     // Write private input file
     let private_input = PrivateInput {
         memory_path: memory_file.clone(),
         trace_path: trace_file.clone(),
-        pedersen: vec![],
-        range_check: vec![],
-        ecdsa: vec![],
+        pedersen,
+        range_check,
+        ecdsa,
     };
 
-    write_json_to_file(private_input, &private_input_file)?;
+    write_to_file(private_input, &private_input_file, format)?;
+
+    // Resolve the `cpu_air_prover` binary to invoke. With the `embedded-prover` feature, this
+    // extracts the binary embedded at build time once per process (cached across calls, rather
+    // than relying on a `PATH` lookup) instead of re-writing it to a fresh temp file on every
+    // proving call.
+    #[cfg(feature = "embedded-prover")]
+    let prover_binary = embedded::extract_prover()?;
+    #[cfg(not(feature = "embedded-prover"))]
+    let prover_binary = PathBuf::from("cpu_air_prover");
 
     Ok(ProverWorkingDirectory {
         _dir: tmp_dir,
+        prover_binary,
         public_input_file,
         private_input_file,
         _memory_file: memory_file,
@@ -149,38 +526,172 @@ fn prepare_prover_files(
     })
 }
 
+fn prepare_verifier_files(
+    proof: &Proof,
+    annotation: &[u8],
+    extra_output: &[u8],
+) -> Result<VerifierWorkingDirectory, ProverError> {
+    let tmp_dir = tempdir()?;
+
+    let tmp_dir_path = tmp_dir.path();
+
+    let proof_file = tmp_dir_path.join("proof.json");
+    let annotation_file = tmp_dir_path.join("annotation.json");
+    let extra_output_file = tmp_dir_path.join("extra_output.json");
+
+    write_to_file(proof, &proof_file, SerializationFormat::Json)?;
+    std::fs::write(&annotation_file, annotation)?;
+    std::fs::write(&extra_output_file, extra_output)?;
+
+    Ok(VerifierWorkingDirectory {
+        _dir: tmp_dir,
+        proof_file,
+        annotation_file,
+        extra_output_file,
+    })
+}
+
 /// Run the Stone Prover on the specified program execution.
 ///
-/// This function abstracts the method used to call the prover. At the moment we invoke
-/// the prover as a subprocess but other methods can be implemented (ex: FFI).
+/// This uses the default [`Backend::Subprocess`] backend. Use [`run_prover_with_backend`] to
+/// select a different backend (ex: FFI).
 ///
 /// * `public_input`: the public prover input generated by the Cairo program.
 /// * `memory`: the memory output of the Cairo program.
 /// * `trace`: the execution trace of the Cairo program.
 /// * `prover_config`: prover configuration.
 /// * `parameters`: prover parameters for the Cairo program.
+/// * `ecdsa_signatures`: signatures for the program's ECDSA builtin instances, one per
+///                       instance. Pass an empty slice for programs that don't use the ECDSA
+///                       builtin.
 pub fn run_prover(
     public_input: &PublicInput,
     memory: &Vec<u8>,
     trace: &Vec<u8>,
     prover_config: &ProverConfig,
     parameters: &ProverParameters,
+    ecdsa_signatures: &[EcdsaSignatureInput],
 ) -> Result<Proof, ProverError> {
-    let prover_working_dir =
-        prepare_prover_files(public_input, memory, trace, prover_config, parameters)?;
-
-    // Call the prover
-    run_prover_from_command_line(
-        &prover_working_dir.public_input_file,
-        &prover_working_dir.private_input_file,
-        &prover_working_dir.prover_config_file,
-        &prover_working_dir.prover_parameter_file,
-        &prover_working_dir.proof_file,
-    )?;
+    run_prover_with_backend(
+        public_input,
+        memory,
+        trace,
+        prover_config,
+        parameters,
+        ecdsa_signatures,
+        Backend::Subprocess,
+    )
+}
 
-    // Load the proof from the generated JSON proof file
-    let proof = read_json_from_file(&prover_working_dir.proof_file)?;
-    Ok(proof)
+/// Run the Stone Prover on the specified program execution, using the given [`Backend`].
+///
+/// Input files are written as JSON. Use [`run_prover_with_options`] to select a different
+/// [`SerializationFormat`].
+///
+/// * `public_input`: the public prover input generated by the Cairo program.
+/// * `memory`: the memory output of the Cairo program.
+/// * `trace`: the execution trace of the Cairo program.
+/// * `prover_config`: prover configuration.
+/// * `parameters`: prover parameters for the Cairo program.
+/// * `ecdsa_signatures`: signatures for the program's ECDSA builtin instances, one per
+///                       instance. Pass an empty slice for programs that don't use the ECDSA
+///                       builtin.
+/// * `backend`: the method used to invoke the prover.
+pub fn run_prover_with_backend(
+    public_input: &PublicInput,
+    memory: &Vec<u8>,
+    trace: &Vec<u8>,
+    prover_config: &ProverConfig,
+    parameters: &ProverParameters,
+    ecdsa_signatures: &[EcdsaSignatureInput],
+    backend: Backend,
+) -> Result<Proof, ProverError> {
+    run_prover_with_options(
+        public_input,
+        memory,
+        trace,
+        prover_config,
+        parameters,
+        ecdsa_signatures,
+        backend,
+        SerializationFormat::Json,
+    )
+}
+
+/// Run the Stone Prover on the specified program execution, using the given [`Backend`] and
+/// [`SerializationFormat`] for the input files.
+///
+/// * `public_input`: the public prover input generated by the Cairo program.
+/// * `memory`: the memory output of the Cairo program.
+/// * `trace`: the execution trace of the Cairo program.
+/// * `prover_config`: prover configuration.
+/// * `parameters`: prover parameters for the Cairo program.
+/// * `ecdsa_signatures`: signatures for the program's ECDSA builtin instances, one per
+///                       instance. Pass an empty slice for programs that don't use the ECDSA
+///                       builtin. [`Backend::Ffi`] has no hook to pass these to
+///                       `cpu_air_prover_run`, so it rejects any non-empty slice instead of
+///                       silently proving without them.
+/// * `backend`: the method used to invoke the prover.
+/// * `format`: the encoding used for the public input, private input, prover config and
+///             parameter files. Every backend accepts only `SerializationFormat::Json` and
+///             returns `ProverError::InvalidArgument` for any other format.
+pub fn run_prover_with_options(
+    public_input: &PublicInput,
+    memory: &Vec<u8>,
+    trace: &Vec<u8>,
+    prover_config: &ProverConfig,
+    parameters: &ProverParameters,
+    ecdsa_signatures: &[EcdsaSignatureInput],
+    backend: Backend,
+    format: SerializationFormat,
+) -> Result<Proof, ProverError> {
+    match backend {
+        Backend::Subprocess => {
+            // `cpu_air_prover` has no flag to select its input codec: it only understands JSON
+            // files.
+            if format != SerializationFormat::Json {
+                return Err(unsupported_subprocess_format_error());
+            }
+
+            let prover_working_dir = prepare_prover_files(
+                public_input,
+                memory,
+                trace,
+                prover_config,
+                parameters,
+                ecdsa_signatures,
+                format,
+            )?;
+
+            // Call the prover
+            run_prover_from_command_line(
+                &prover_working_dir.prover_binary,
+                &prover_working_dir.public_input_file,
+                &prover_working_dir.private_input_file,
+                &prover_working_dir.prover_config_file,
+                &prover_working_dir.prover_parameter_file,
+                &prover_working_dir.proof_file,
+            )?;
+
+            // Load the proof from the generated JSON proof file
+            let proof = read_json_from_file(&prover_working_dir.proof_file)?;
+            Ok(proof)
+        }
+        #[cfg(feature = "ffi")]
+        Backend::Ffi => {
+            // `ffi::run_prover_ffi` always serializes its inputs with `serde_json` before
+            // calling into the native library, so a caller-requested `MsgPack` must be rejected
+            // up front rather than silently proved as JSON anyway.
+            if format != SerializationFormat::Json {
+                return Err(unsupported_ffi_format_error());
+            }
+            let ecdsa_segment = &public_input.memory_segments.ecdsa;
+            if !ecdsa_signatures.is_empty() || ecdsa_segment.stop_ptr > ecdsa_segment.begin_addr {
+                return Err(unsupported_ffi_ecdsa_error());
+            }
+            ffi::run_prover_ffi(public_input, memory, trace, prover_config, parameters)
+        }
+    }
 }
 
 /// Run the Stone Prover on the specified program execution, asynchronously.
@@ -188,37 +699,225 @@ pub fn run_prover(
 /// The main difference from the synchronous implementation is that the prover process
 /// is spawned asynchronously using `tokio::process::Command`.
 ///
-/// This function abstracts the method used to call the prover. At the moment we invoke
-/// the prover as a subprocess but other methods can be implemented (ex: FFI).
+/// This uses the default [`Backend::Subprocess`] backend. Use [`run_prover_async_with_backend`]
+/// to select a different backend (ex: FFI).
 ///
 /// * `public_input`: the public prover input generated by the Cairo program.
 /// * `memory`: the memory output of the Cairo program.
 /// * `trace`: the execution trace of the Cairo program.
 /// * `prover_config`: prover configuration.
 /// * `parameters`: prover parameters for the Cairo program.
+/// * `ecdsa_signatures`: signatures for the program's ECDSA builtin instances, one per
+///                       instance. Pass an empty slice for programs that don't use the ECDSA
+///                       builtin.
 pub async fn run_prover_async(
     public_input: &PublicInput,
     memory: &Vec<u8>,
     trace: &Vec<u8>,
     prover_config: &ProverConfig,
     parameters: &ProverParameters,
+    ecdsa_signatures: &[EcdsaSignatureInput],
+) -> Result<Proof, ProverError> {
+    run_prover_async_with_backend(
+        public_input,
+        memory,
+        trace,
+        prover_config,
+        parameters,
+        ecdsa_signatures,
+        Backend::Subprocess,
+    )
+    .await
+}
+
+/// Run the Stone Prover on the specified program execution, asynchronously, using the given
+/// [`Backend`].
+///
+/// Input files are written as JSON. Use [`run_prover_async_with_options`] to select a
+/// different [`SerializationFormat`].
+///
+/// * `public_input`: the public prover input generated by the Cairo program.
+/// * `memory`: the memory output of the Cairo program.
+/// * `trace`: the execution trace of the Cairo program.
+/// * `prover_config`: prover configuration.
+/// * `parameters`: prover parameters for the Cairo program.
+/// * `ecdsa_signatures`: signatures for the program's ECDSA builtin instances, one per
+///                       instance. Pass an empty slice for programs that don't use the ECDSA
+///                       builtin.
+/// * `backend`: the method used to invoke the prover.
+pub async fn run_prover_async_with_backend(
+    public_input: &PublicInput,
+    memory: &Vec<u8>,
+    trace: &Vec<u8>,
+    prover_config: &ProverConfig,
+    parameters: &ProverParameters,
+    ecdsa_signatures: &[EcdsaSignatureInput],
+    backend: Backend,
+) -> Result<Proof, ProverError> {
+    run_prover_async_with_options(
+        public_input,
+        memory,
+        trace,
+        prover_config,
+        parameters,
+        ecdsa_signatures,
+        backend,
+        SerializationFormat::Json,
+    )
+    .await
+}
+
+/// Run the Stone Prover on the specified program execution, asynchronously, using the given
+/// [`Backend`] and [`SerializationFormat`] for the input files.
+///
+/// * `public_input`: the public prover input generated by the Cairo program.
+/// * `memory`: the memory output of the Cairo program.
+/// * `trace`: the execution trace of the Cairo program.
+/// * `prover_config`: prover configuration.
+/// * `parameters`: prover parameters for the Cairo program.
+/// * `ecdsa_signatures`: signatures for the program's ECDSA builtin instances, one per
+///                       instance. Pass an empty slice for programs that don't use the ECDSA
+///                       builtin. [`Backend::Ffi`] has no hook to pass these to
+///                       `cpu_air_prover_run`, so it rejects any non-empty slice instead of
+///                       silently proving without them.
+/// * `backend`: the method used to invoke the prover.
+/// * `format`: the encoding used for the public input, private input, prover config and
+///             parameter files. Every backend accepts only `SerializationFormat::Json` and
+///             returns `ProverError::InvalidArgument` for any other format.
+pub async fn run_prover_async_with_options(
+    public_input: &PublicInput,
+    memory: &Vec<u8>,
+    trace: &Vec<u8>,
+    prover_config: &ProverConfig,
+    parameters: &ProverParameters,
+    ecdsa_signatures: &[EcdsaSignatureInput],
+    backend: Backend,
+    format: SerializationFormat,
 ) -> Result<Proof, ProverError> {
-    let prover_working_dir =
-        prepare_prover_files(public_input, memory, trace, prover_config, parameters)?;
-
-    // Call the prover
-    run_prover_from_command_line_async(
-        &prover_working_dir.public_input_file,
-        &prover_working_dir.private_input_file,
-        &prover_working_dir.prover_config_file,
-        &prover_working_dir.prover_parameter_file,
-        &prover_working_dir.proof_file,
+    match backend {
+        Backend::Subprocess => {
+            // `cpu_air_prover` has no flag to select its input codec: it only understands JSON
+            // files.
+            if format != SerializationFormat::Json {
+                return Err(unsupported_subprocess_format_error());
+            }
+
+            let prover_working_dir = prepare_prover_files(
+                public_input,
+                memory,
+                trace,
+                prover_config,
+                parameters,
+                ecdsa_signatures,
+                format,
+            )?;
+
+            // Call the prover
+            run_prover_from_command_line_async(
+                &prover_working_dir.prover_binary,
+                &prover_working_dir.public_input_file,
+                &prover_working_dir.private_input_file,
+                &prover_working_dir.prover_config_file,
+                &prover_working_dir.prover_parameter_file,
+                &prover_working_dir.proof_file,
+            )
+            .await?;
+
+            // Load the proof from the generated JSON proof file
+            let proof = read_json_from_file(&prover_working_dir.proof_file)?;
+            Ok(proof)
+        }
+        #[cfg(feature = "ffi")]
+        Backend::Ffi => {
+            // `ffi::run_prover_ffi` always serializes its inputs with `serde_json` before
+            // calling into the native library, so a caller-requested `MsgPack` must be rejected
+            // up front rather than silently proved as JSON anyway.
+            if format != SerializationFormat::Json {
+                return Err(unsupported_ffi_format_error());
+            }
+            let ecdsa_segment = &public_input.memory_segments.ecdsa;
+            if !ecdsa_signatures.is_empty() || ecdsa_segment.stop_ptr > ecdsa_segment.begin_addr {
+                return Err(unsupported_ffi_ecdsa_error());
+            }
+            ffi::run_prover_ffi(public_input, memory, trace, prover_config, parameters)
+        }
+    }
+}
+
+/// Run the Stone Verifier on the specified proof.
+///
+/// This function abstracts the method used to call the verifier. At the moment we invoke
+/// the verifier as a subprocess, mirroring `run_prover`.
+///
+/// * `proof`: the proof to verify, as generated by `run_prover`.
+/// * `annotation`: the annotation file contents produced alongside the proof.
+/// * `extra_output`: the extra output file contents produced alongside the proof.
+///
+/// Returns `true` if the proof is valid, `false` otherwise.
+pub fn run_verifier(
+    proof: &Proof,
+    annotation: &[u8],
+    extra_output: &[u8],
+) -> Result<bool, ProverError> {
+    let verifier_working_dir = prepare_verifier_files(proof, annotation, extra_output)?;
+
+    run_verifier_from_command_line(
+        &verifier_working_dir.proof_file,
+        &verifier_working_dir.annotation_file,
+        &verifier_working_dir.extra_output_file,
     )
-    .await?;
+}
+
+/// Run the Stone Verifier on the specified proof, asynchronously.
+///
+/// The main difference from the synchronous implementation is that the verifier process
+/// is spawned asynchronously using `tokio::process::Command`.
+///
+/// * `proof`: the proof to verify, as generated by `run_prover`.
+/// * `annotation`: the annotation file contents produced alongside the proof.
+/// * `extra_output`: the extra output file contents produced alongside the proof.
+///
+/// Returns `true` if the proof is valid, `false` otherwise.
+pub async fn run_verifier_async(
+    proof: &Proof,
+    annotation: &[u8],
+    extra_output: &[u8],
+) -> Result<bool, ProverError> {
+    let verifier_working_dir = prepare_verifier_files(proof, annotation, extra_output)?;
 
-    // Load the proof from the generated JSON proof file
-    let proof = read_json_from_file(&prover_working_dir.proof_file)?;
-    Ok(proof)
+    run_verifier_from_command_line_async(
+        &verifier_working_dir.proof_file,
+        &verifier_working_dir.annotation_file,
+        &verifier_working_dir.extra_output_file,
+    )
+    .await
+}
+
+/// Test fixtures shared between this module's tests and sibling modules' (ex: `job_manager`),
+/// so the two don't each keep their own copy of the same fixture-loading boilerplate.
+#[cfg(test)]
+pub(crate) mod test_support {
+    use crate::toolkit::{get_fixture_path, read_json_from_file};
+
+    use super::ProverJob;
+
+    /// Builds a `ProverJob` from the fibonacci fixture files.
+    pub(crate) fn fibonacci_job() -> ProverJob {
+        let public_input_file = get_fixture_path("fibonacci/fibonacci_public_input.json");
+        let prover_config_file = get_fixture_path("fibonacci/cpu_air_prover_config.json");
+        let parameter_file = get_fixture_path("fibonacci/cpu_air_params.json");
+        let memory_file = get_fixture_path("fibonacci/fibonacci_memory.bin");
+        let trace_file = get_fixture_path("fibonacci/fibonacci_trace.bin");
+
+        ProverJob {
+            public_input: read_json_from_file(public_input_file).unwrap(),
+            memory: std::fs::read(memory_file).unwrap(),
+            trace: std::fs::read(trace_file).unwrap(),
+            prover_config: read_json_from_file(prover_config_file).unwrap(),
+            parameters: read_json_from_file(parameter_file).unwrap(),
+            ecdsa_signatures: vec![],
+        }
+    }
 }
 
 #[cfg(test)]
@@ -228,6 +927,7 @@ mod test {
     use crate::models::{PrivateInput, Proof};
     use crate::toolkit::{get_fixture_path, read_json_from_file};
 
+    use super::test_support::fibonacci_job;
     use super::*;
 
     /// Reads and deserializes a JSON proof file.
@@ -236,6 +936,159 @@ mod test {
         proof
     }
 
+    #[test]
+    fn test_derive_range_check_input() {
+        // Two cells (addresses 10 and 11) within the segment, one (address 12) just past it.
+        let memory = HashMap::from([
+            (10u64, [1u8; CAIRO_FELT_SIZE]),
+            (11u64, [2u8; CAIRO_FELT_SIZE]),
+            (12u64, [3u8; CAIRO_FELT_SIZE]),
+        ]);
+        let segment = MemorySegment {
+            begin_addr: 10,
+            stop_ptr: 12,
+        };
+
+        let range_check = derive_range_check_input(&segment, &memory).unwrap();
+
+        assert_eq!(range_check.len(), 2);
+        assert_eq!(range_check[0].index, 0);
+        assert_eq!(range_check[0].value, felt_to_hex(&[1u8; CAIRO_FELT_SIZE]));
+        assert_eq!(range_check[1].index, 1);
+        assert_eq!(range_check[1].value, felt_to_hex(&[2u8; CAIRO_FELT_SIZE]));
+    }
+
+    #[test]
+    fn test_derive_range_check_input_errors_on_missing_cell() {
+        // Segment covers addresses 10..12, but address 11 has no entry in `memory`.
+        let memory = HashMap::from([(10u64, [1u8; CAIRO_FELT_SIZE])]);
+        let segment = MemorySegment {
+            begin_addr: 10,
+            stop_ptr: 12,
+        };
+
+        let result = derive_range_check_input(&segment, &memory);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_derive_pedersen_input() {
+        // One instance (addresses 20, 21, 22): x, y, and the output cell computed by the
+        // prover, which isn't part of the private input.
+        let memory = HashMap::from([
+            (20u64, [1u8; CAIRO_FELT_SIZE]),
+            (21u64, [2u8; CAIRO_FELT_SIZE]),
+            (22u64, [3u8; CAIRO_FELT_SIZE]),
+        ]);
+        let segment = MemorySegment {
+            begin_addr: 20,
+            stop_ptr: 23,
+        };
+
+        let pedersen = derive_pedersen_input(&segment, &memory).unwrap();
+
+        assert_eq!(pedersen.len(), 1);
+        assert_eq!(pedersen[0].index, 0);
+        assert_eq!(pedersen[0].x, felt_to_hex(&[1u8; CAIRO_FELT_SIZE]));
+        assert_eq!(pedersen[0].y, felt_to_hex(&[2u8; CAIRO_FELT_SIZE]));
+    }
+
+    #[test]
+    fn test_derive_pedersen_input_errors_on_missing_cell() {
+        // Segment covers one instance (addresses 20, 21, 22), but address 21 (`y`) has no entry
+        // in `memory`.
+        let memory = HashMap::from([(20u64, [1u8; CAIRO_FELT_SIZE])]);
+        let segment = MemorySegment {
+            begin_addr: 20,
+            stop_ptr: 23,
+        };
+
+        let result = derive_pedersen_input(&segment, &memory);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_derive_ecdsa_input_pairs_signatures_by_index() {
+        // Two instances: (30, 31) and (32, 33).
+        let memory = HashMap::from([
+            (30u64, [1u8; CAIRO_FELT_SIZE]),
+            (31u64, [2u8; CAIRO_FELT_SIZE]),
+            (32u64, [3u8; CAIRO_FELT_SIZE]),
+            (33u64, [4u8; CAIRO_FELT_SIZE]),
+        ]);
+        let segment = MemorySegment {
+            begin_addr: 30,
+            stop_ptr: 34,
+        };
+        let signatures = [
+            EcdsaSignatureInput {
+                r: "r0".to_string(),
+                w: "w0".to_string(),
+            },
+            EcdsaSignatureInput {
+                r: "r1".to_string(),
+                w: "w1".to_string(),
+            },
+        ];
+
+        let ecdsa = derive_ecdsa_input(&segment, &memory, &signatures).unwrap();
+
+        assert_eq!(ecdsa.len(), 2);
+        assert_eq!(ecdsa[0].index, 0);
+        assert_eq!(ecdsa[0].pubkey, felt_to_hex(&[1u8; CAIRO_FELT_SIZE]));
+        assert_eq!(ecdsa[0].message, felt_to_hex(&[2u8; CAIRO_FELT_SIZE]));
+        assert_eq!(ecdsa[0].signature_input.r, "r0");
+        assert_eq!(ecdsa[0].signature_input.w, "w0");
+        assert_eq!(ecdsa[1].index, 1);
+        assert_eq!(ecdsa[1].signature_input.r, "r1");
+        assert_eq!(ecdsa[1].signature_input.w, "w1");
+    }
+
+    #[test]
+    fn test_derive_ecdsa_input_errors_on_missing_signature() {
+        // One instance (30, 31), but no signature supplied for it.
+        let memory = HashMap::from([
+            (30u64, [1u8; CAIRO_FELT_SIZE]),
+            (31u64, [2u8; CAIRO_FELT_SIZE]),
+        ]);
+        let segment = MemorySegment {
+            begin_addr: 30,
+            stop_ptr: 32,
+        };
+
+        let result = derive_ecdsa_input(&segment, &memory, &[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_derive_ecdsa_input_errors_on_missing_cell() {
+        // Segment covers one instance (addresses 30, 31), but address 31 (`message`) has no
+        // entry in `memory`.
+        let memory = HashMap::from([(30u64, [1u8; CAIRO_FELT_SIZE])]);
+        let segment = MemorySegment {
+            begin_addr: 30,
+            stop_ptr: 32,
+        };
+
+        let result = derive_ecdsa_input(&segment, &memory, &[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_memory_cells_rejects_partial_trailing_cell() {
+        // One full cell (40 bytes) followed by a partial, malformed cell.
+        let mut memory = vec![0u8; CAIRO_MEMORY_CELL_SIZE];
+        memory.extend_from_slice(&[0u8; CAIRO_MEMORY_CELL_SIZE - 1]);
+
+        let result = parse_memory_cells(&memory);
+
+        assert!(result.is_err());
+    }
+
     /// Check that the Stone Prover command-line wrapper works.
     #[test]
     fn test_run_prover_from_command_line() {
@@ -265,6 +1118,7 @@ mod test {
 
         let output_file = NamedTempFile::new().expect("Creating output file failed");
         run_prover_from_command_line(
+            Path::new("cpu_air_prover"),
             &public_input_file,
             private_input_file.path(),
             &prover_config_file,
@@ -305,6 +1159,7 @@ mod test {
             &trace,
             &prover_config,
             &prover_parameters,
+            &[],
         )
         .unwrap();
 
@@ -338,6 +1193,7 @@ mod test {
             &trace,
             &prover_config,
             &prover_parameters,
+            &[],
         )
         .await
         .unwrap();
@@ -346,4 +1202,210 @@ mod test {
         let expected_proof = read_proof_file(expected_proof_file);
         assert_eq!(proof.proof_hex, expected_proof.proof_hex);
     }
+
+    /// End-to-end check that a freshly generated proof verifies successfully.
+    #[test]
+    fn test_prove_then_verify() {
+        let public_input_file = get_fixture_path("fibonacci/fibonacci_public_input.json");
+        let prover_config_file = get_fixture_path("fibonacci/cpu_air_prover_config.json");
+        let parameter_file = get_fixture_path("fibonacci/cpu_air_params.json");
+        let memory_file = get_fixture_path("fibonacci/fibonacci_memory.bin");
+        let trace_file = get_fixture_path("fibonacci/fibonacci_trace.bin");
+        let annotation_file = get_fixture_path("fibonacci/fibonacci_annotation.json");
+        let extra_output_file = get_fixture_path("fibonacci/fibonacci_extra_output.json");
+
+        let public_input: PublicInput = read_json_from_file(public_input_file).unwrap();
+        let prover_config: ProverConfig = read_json_from_file(prover_config_file).unwrap();
+        let prover_parameters: ProverParameters = read_json_from_file(parameter_file).unwrap();
+        let memory = std::fs::read(memory_file).unwrap();
+        let trace = std::fs::read(trace_file).unwrap();
+        let annotation = std::fs::read(annotation_file).unwrap();
+        let extra_output = std::fs::read(extra_output_file).unwrap();
+
+        // Add build dir to path for the duration of the test
+        let path = std::env::var("PATH").unwrap_or_default();
+        let build_dir = env!("OUT_DIR");
+        std::env::set_var("PATH", format!("{build_dir}:{path}"));
+
+        let proof = run_prover(
+            &public_input,
+            &memory,
+            &trace,
+            &prover_config,
+            &prover_parameters,
+            &[],
+        )
+        .unwrap();
+
+        let is_valid = run_verifier(&proof, &annotation, &extra_output).unwrap();
+        assert!(is_valid);
+    }
+
+    /// End-to-end check, against a program that actually uses the Pedersen and range-check
+    /// builtins, that `derive_pedersen_input`/`derive_range_check_input`'s `felt_to_hex` encoding
+    /// and assumed memory layout match what `cpu_air_prover` expects. The synthetic unit tests
+    /// above only check the derivation logic against a hand-built `HashMap`; this is the one that
+    /// would catch a wrong layout assumption producing a proof `cpu_air_prover` accepts but that
+    /// doesn't actually attest to the right computation.
+    #[test]
+    fn test_prove_then_verify_with_builtins() {
+        let public_input_file = get_fixture_path("builtins/builtins_public_input.json");
+        let prover_config_file = get_fixture_path("builtins/cpu_air_prover_config.json");
+        let parameter_file = get_fixture_path("builtins/cpu_air_params.json");
+        let memory_file = get_fixture_path("builtins/builtins_memory.bin");
+        let trace_file = get_fixture_path("builtins/builtins_trace.bin");
+        let annotation_file = get_fixture_path("builtins/builtins_annotation.json");
+        let extra_output_file = get_fixture_path("builtins/builtins_extra_output.json");
+
+        let public_input: PublicInput = read_json_from_file(public_input_file).unwrap();
+        let prover_config: ProverConfig = read_json_from_file(prover_config_file).unwrap();
+        let prover_parameters: ProverParameters = read_json_from_file(parameter_file).unwrap();
+        let memory = std::fs::read(memory_file).unwrap();
+        let trace = std::fs::read(trace_file).unwrap();
+        let annotation = std::fs::read(annotation_file).unwrap();
+        let extra_output = std::fs::read(extra_output_file).unwrap();
+
+        // Add build dir to path for the duration of the test
+        let path = std::env::var("PATH").unwrap_or_default();
+        let build_dir = env!("OUT_DIR");
+        std::env::set_var("PATH", format!("{build_dir}:{path}"));
+
+        let proof = run_prover(
+            &public_input,
+            &memory,
+            &trace,
+            &prover_config,
+            &prover_parameters,
+            &[],
+        )
+        .unwrap();
+
+        let is_valid = run_verifier(&proof, &annotation, &extra_output).unwrap();
+        assert!(is_valid);
+    }
+
+    #[test]
+    fn test_run_prover_with_msgpack_format() {
+        let public_input_file = get_fixture_path("fibonacci/fibonacci_public_input.json");
+        let prover_config_file = get_fixture_path("fibonacci/cpu_air_prover_config.json");
+        let parameter_file = get_fixture_path("fibonacci/cpu_air_params.json");
+        let memory_file = get_fixture_path("fibonacci/fibonacci_memory.bin");
+        let trace_file = get_fixture_path("fibonacci/fibonacci_trace.bin");
+
+        let public_input: PublicInput = read_json_from_file(public_input_file).unwrap();
+        let prover_config: ProverConfig = read_json_from_file(prover_config_file).unwrap();
+        let prover_parameters: ProverParameters = read_json_from_file(parameter_file).unwrap();
+        let memory = std::fs::read(memory_file).unwrap();
+        let trace = std::fs::read(trace_file).unwrap();
+
+        // `cpu_air_prover` only speaks JSON: asking for MsgPack with Backend::Subprocess must
+        // be rejected rather than silently handed to the prover as garbage.
+        let result = run_prover_with_options(
+            &public_input,
+            &memory,
+            &trace,
+            &prover_config,
+            &prover_parameters,
+            &[],
+            Backend::Subprocess,
+            SerializationFormat::MsgPack,
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_run_prover_with_ffi_and_msgpack_format() {
+        let public_input_file = get_fixture_path("fibonacci/fibonacci_public_input.json");
+        let prover_config_file = get_fixture_path("fibonacci/cpu_air_prover_config.json");
+        let parameter_file = get_fixture_path("fibonacci/cpu_air_params.json");
+        let memory_file = get_fixture_path("fibonacci/fibonacci_memory.bin");
+        let trace_file = get_fixture_path("fibonacci/fibonacci_trace.bin");
+
+        let public_input: PublicInput = read_json_from_file(public_input_file).unwrap();
+        let prover_config: ProverConfig = read_json_from_file(prover_config_file).unwrap();
+        let prover_parameters: ProverParameters = read_json_from_file(parameter_file).unwrap();
+        let memory = std::fs::read(memory_file).unwrap();
+        let trace = std::fs::read(trace_file).unwrap();
+
+        // `ffi::run_prover_ffi` always serializes its inputs to JSON before calling into the
+        // native library: asking for MsgPack with Backend::Ffi must be rejected rather than
+        // silently proved as JSON anyway, which would make the caller's format choice a no-op.
+        let result = run_prover_with_options(
+            &public_input,
+            &memory,
+            &trace,
+            &prover_config,
+            &prover_parameters,
+            &[],
+            Backend::Ffi,
+            SerializationFormat::MsgPack,
+        );
+
+        assert!(result.is_err());
+    }
+
+    /// Sanity check that the MsgPack codec itself round-trips, independent of which backend
+    /// (if any) is allowed to consume it.
+    #[test]
+    fn test_msgpack_round_trip() {
+        use crate::toolkit::{read_from_file, write_to_file};
+
+        let public_input_file = get_fixture_path("fibonacci/fibonacci_public_input.json");
+        let public_input: PublicInput = read_json_from_file(public_input_file).unwrap();
+
+        let msgpack_file = NamedTempFile::new().expect("Creating temporary msgpack file failed");
+        write_to_file(
+            &public_input,
+            msgpack_file.path(),
+            SerializationFormat::MsgPack,
+        )
+        .unwrap();
+        let round_tripped: PublicInput =
+            read_from_file(msgpack_file.path(), SerializationFormat::MsgPack).unwrap();
+
+        assert_eq!(round_tripped.n_steps, public_input.n_steps);
+        assert_eq!(round_tripped.layout, public_input.layout);
+    }
+
+    #[tokio::test]
+    async fn test_run_prover_batch() {
+        // Add build dir to path for the duration of the test
+        let path = std::env::var("PATH").unwrap_or_default();
+        let build_dir = env!("OUT_DIR");
+        std::env::set_var("PATH", format!("{build_dir}:{path}"));
+
+        // Corrupt the middle job's trace so it fails, giving each job a distinguishable, known
+        // outcome tied to its position in `jobs`. `buffer_unordered` yields results in completion
+        // order, not input order, so if `run_prover_batch` stopped sorting results back into input order, this
+        // `Err` would surface at the wrong index and the assertions below would fail.
+        let mut broken_job = fibonacci_job();
+        broken_job.trace.truncate(4);
+
+        let jobs = vec![fibonacci_job(), broken_job, fibonacci_job()];
+
+        let results = run_prover_batch(jobs, 2).await.unwrap();
+
+        let expected_proof_file = get_fixture_path("fibonacci/fibonacci_proof.json");
+        let expected_proof = read_proof_file(expected_proof_file);
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results[0].as_ref().unwrap().proof_hex,
+            expected_proof.proof_hex
+        );
+        assert!(results[1].is_err());
+        assert_eq!(
+            results[2].as_ref().unwrap().proof_hex,
+            expected_proof.proof_hex
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_prover_batch_rejects_zero_concurrency() {
+        let result = run_prover_batch(vec![fibonacci_job()], 0).await;
+
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,43 @@
+//! Embeds the `cpu_air_prover` binary into this crate so that downstream binaries don't need
+//! `cpu_air_prover` to already be on `PATH`.
+//!
+//! Only compiled in with the `embedded-prover` cargo feature.
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// The `cpu_air_prover` executable, embedded at build time.
+///
+/// `CPU_AIR_PROVER_PATH` is set by the crate's build script to the path of the binary to embed.
+static CPU_AIR_PROVER_BYTES: &[u8] = include_bytes!(env!("CPU_AIR_PROVER_PATH"));
+
+/// Caches the path the embedded binary was extracted to, so that `extract_prover` only writes it
+/// to disk once per process rather than once per proving call. `Err` is cached as its message
+/// (`std::io::Error` isn't `Clone`) and rebuilt into an `io::Error` on each call that needs it.
+static PROVER_PATH: OnceLock<Result<PathBuf, String>> = OnceLock::new();
+
+/// Returns the path to the embedded `cpu_air_prover` binary, extracting it to a process-lifetime
+/// temp directory on the first call and reusing that path on every subsequent call.
+pub(super) fn extract_prover() -> Result<PathBuf, std::io::Error> {
+    PROVER_PATH
+        .get_or_init(|| extract_prover_once().map_err(|err| err.to_string()))
+        .clone()
+        .map_err(|message| std::io::Error::new(std::io::ErrorKind::Other, message))
+}
+
+fn extract_prover_once() -> Result<PathBuf, std::io::Error> {
+    // Leaked rather than held in a `TempDir`: the binary must stay on disk for the life of the
+    // process, not just for the duration of this function.
+    let dir = tempfile::tempdir()?;
+    let prover_path = dir.path().join("cpu_air_prover");
+    std::fs::write(&prover_path, CPU_AIR_PROVER_BYTES)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&prover_path, std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    std::mem::forget(dir);
+    Ok(prover_path)
+}
@@ -0,0 +1,77 @@
+//! Bindings to the Stone Prover shared library.
+//!
+//! This backend is only compiled in with the `ffi` cargo feature, since it requires linking
+//! against the native `libcpu_air_prover` shared library at build time.
+
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+
+use crate::error::ProverError;
+use crate::models::{Proof, ProverConfig, ProverParameters, PublicInput};
+
+extern "C" {
+    /// Runs the Stone Prover on the given inputs, entirely in memory.
+    ///
+    /// On success, writes a newly allocated, NUL-terminated JSON string containing the proof
+    /// to `out_proof_json` and returns 0. The caller must free it with
+    /// `cpu_air_prover_free_string`. On failure, returns a non-zero status and leaves
+    /// `out_proof_json` untouched.
+    fn cpu_air_prover_run(
+        public_input_json: *const c_char,
+        memory: *const u8,
+        memory_len: usize,
+        trace: *const u8,
+        trace_len: usize,
+        prover_config_json: *const c_char,
+        parameters_json: *const c_char,
+        out_proof_json: *mut *mut c_char,
+    ) -> c_int;
+
+    /// Frees a string allocated by `cpu_air_prover_run`.
+    fn cpu_air_prover_free_string(ptr: *mut c_char);
+}
+
+/// Run the Stone Prover via the FFI backend, passing the public input, memory and trace in
+/// memory rather than round-tripping them through temporary files.
+pub(super) fn run_prover_ffi(
+    public_input: &PublicInput,
+    memory: &[u8],
+    trace: &[u8],
+    prover_config: &ProverConfig,
+    parameters: &ProverParameters,
+) -> Result<Proof, ProverError> {
+    let public_input_json = CString::new(serde_json::to_vec(public_input)?)?;
+    let prover_config_json = CString::new(serde_json::to_vec(prover_config)?)?;
+    let parameters_json = CString::new(serde_json::to_vec(parameters)?)?;
+
+    let mut out_proof_json: *mut c_char = std::ptr::null_mut();
+
+    // Safety: all pointers passed to `cpu_air_prover_run` are valid for the duration of the
+    // call, and `out_proof_json` is only read back after checking the returned status.
+    let status = unsafe {
+        cpu_air_prover_run(
+            public_input_json.as_ptr(),
+            memory.as_ptr(),
+            memory.len(),
+            trace.as_ptr(),
+            trace.len(),
+            prover_config_json.as_ptr(),
+            parameters_json.as_ptr(),
+            &mut out_proof_json,
+        )
+    };
+
+    if status != 0 || out_proof_json.is_null() {
+        return Err(ProverError::FfiError(status));
+    }
+
+    // Safety: `out_proof_json` was just set to a non-null, NUL-terminated string by
+    // `cpu_air_prover_run`, which we free exactly once below.
+    let proof_json = unsafe { CStr::from_ptr(out_proof_json) }
+        .to_string_lossy()
+        .into_owned();
+    unsafe { cpu_air_prover_free_string(out_proof_json) };
+
+    let proof: Proof = serde_json::from_str(&proof_json)?;
+    Ok(proof)
+}
@@ -0,0 +1,96 @@
+//! Helpers for reading and writing the prover's serde-derived models, and for locating test
+//! fixtures.
+
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::ProverError;
+
+/// Selects the on-disk encoding used for the prover's input files. The proof produced by the
+/// prover is always read back as JSON.
+///
+/// * `Json`: human-readable, the default.
+/// * `MsgPack`: compact binary encoding. Materially shrinks the on-disk payload and parse time
+///             for large models (ex: `PublicInput::public_memory`), at the cost of the file no
+///             longer being human-readable.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum SerializationFormat {
+    #[default]
+    Json,
+    MsgPack,
+}
+
+/// Serializes `value` to `path` using `format`.
+pub fn write_to_file<T: Serialize>(
+    value: T,
+    path: impl AsRef<Path>,
+    format: SerializationFormat,
+) -> Result<(), ProverError> {
+    match format {
+        SerializationFormat::Json => write_json_to_file(value, path).map_err(ProverError::from),
+        SerializationFormat::MsgPack => write_msgpack_to_file(value, path),
+    }
+}
+
+/// Deserializes a value of type `T` from `path`, encoded as `format`.
+pub fn read_from_file<T: DeserializeOwned>(
+    path: impl AsRef<Path>,
+    format: SerializationFormat,
+) -> Result<T, ProverError> {
+    match format {
+        SerializationFormat::Json => read_json_from_file(path).map_err(ProverError::from),
+        SerializationFormat::MsgPack => read_msgpack_from_file(path),
+    }
+}
+
+/// Serializes `value` to `path` as JSON.
+pub fn write_json_to_file<T: Serialize>(
+    value: T,
+    path: impl AsRef<Path>,
+) -> Result<(), std::io::Error> {
+    let file = std::fs::File::create(path)?;
+    serde_json::to_writer(file, &value)?;
+    Ok(())
+}
+
+/// Deserializes a value of type `T` from the JSON file at `path`.
+pub fn read_json_from_file<T: DeserializeOwned>(
+    path: impl AsRef<Path>,
+) -> Result<T, std::io::Error> {
+    let file = std::fs::File::open(path)?;
+    let value = serde_json::from_reader(file)?;
+    Ok(value)
+}
+
+/// Serializes `value` to `path` as MessagePack.
+pub fn write_msgpack_to_file<T: Serialize>(
+    value: T,
+    path: impl AsRef<Path>,
+) -> Result<(), ProverError> {
+    let bytes = rmp_serde::to_vec(&value)?;
+    std::fs::write(path, bytes)?;
+    Ok(())
+}
+
+/// Deserializes a value of type `T` from the MessagePack file at `path`.
+pub fn read_msgpack_from_file<T: DeserializeOwned>(
+    path: impl AsRef<Path>,
+) -> Result<T, ProverError> {
+    let bytes = std::fs::read(path)?;
+    let value = rmp_serde::from_slice(&bytes)?;
+    Ok(value)
+}
+
+/// Returns the path to the fixture file `name`, under the crate's `test-data` directory.
+pub fn get_fixture_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("test-data")
+        .join(name)
+}
+
+/// Reads the fixture file `name` into a string.
+pub fn load_fixture(name: &str) -> String {
+    std::fs::read_to_string(get_fixture_path(name)).expect("Could not read fixture file")
+}